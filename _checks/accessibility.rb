@@ -0,0 +1,27 @@
+# Static accessibility checks run by `rake check` on top of html-proofer's own
+# (which already reports images without alt text).
+class AccessibilityCheck < ::HTMLProofer::Check
+  def run
+    html = @html.at_css('html')
+    add_issue("html element is missing the lang attribute") if html.nil? || html['lang'].to_s.strip.empty?
+
+    level = 0
+    @html.css('h1, h2, h3, h4, h5, h6').each do |heading|
+      current = heading.name[1].to_i
+      add_issue("heading level skips from h#{level} to h#{current}", line: heading.line) if level > 0 && current > level + 1
+      level = current
+    end
+
+    seen = {}
+    @html.css('[id]').each do |node|
+      add_issue("duplicate id '#{node['id']}'", line: node.line) if seen[node['id']]
+      seen[node['id']] = true
+    end
+
+    @html.css('a[href]').each do |link|
+      next unless link.text.strip.empty?
+      next if link['aria-label'] || link['title'] || link.at_css('img[alt]')
+      add_issue("link to #{link['href']} has no accessible text", line: link.line)
+    end
+  end
+end
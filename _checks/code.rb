@@ -0,0 +1,55 @@
+require 'open3'
+require 'shellwords'
+require 'tmpdir'
+
+# Compiles the fenced code blocks marked with a `{: .check}` line after the closing
+# fence, run by `rake check code=true`. The command for each language is configured
+# under `code_check:` in _config.yml, with %{file} and %{dir} for the snippet file
+# and a scratch directory.
+module CodeCheck
+  FENCE = /^\s*(```|~~~)\s*(\w*)/
+  MARKER = /^\s*\{:[^}]*\.check\b[^}]*\}\s*$/
+
+  def self.run(files, commands)
+    files.flat_map do |file|
+      blocks(File.read(file)).map do |line, lang, code|
+        command = commands[lang]
+        next "#{file}:#{line}: no code_check command for '#{lang}'" unless command
+        output = compile(command, lang, code)
+        "#{file}:#{line}: #{lang} snippet failed:\n#{output.gsub(/^/, '    ')}" if output
+      end.compact
+    end
+  end
+
+  # Returns [line number, language, code] for the marked code blocks.
+  def self.blocks(source)
+    found = []
+    lines = source.lines
+    opening = nil
+    lines.each_with_index do |line, index|
+      next unless line =~ FENCE
+      if opening.nil?
+        opening = [index, $2]
+        next
+      end
+      start, lang = opening
+      opening = nil
+      marker = lines[(index + 1)..-1].find { |next_line| !next_line.strip.empty? }
+      found << [start + 1, lang, lines[(start + 1)...index].join] if marker =~ MARKER
+    end
+    found
+  end
+
+  # Returns the compiler output if the snippet fails to compile.
+  def self.compile(command, lang, code)
+    Dir.mktmpdir do |dir|
+      file = File.join(dir, "snippet.#{lang}")
+      File.write(file, code)
+      args = Shellwords.split(command).map { |arg| arg.gsub('%{file}', file).gsub('%{dir}', dir) }
+      out, status = Open3.capture2e(*args, chdir: dir)
+      status.success? ? nil : out.strip
+    end
+  rescue Errno::ENOENT => e
+    e.message
+  end
+end
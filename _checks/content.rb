@@ -0,0 +1,62 @@
+require 'uri'
+
+# Content lint rules run by `rake check` over the posts, configured under `lint:`
+# in _config.yml with a severity of error, warn or off per rule. Posts can skip
+# rules by listing them in `lint_ignore:` front matter.
+module ContentCheck
+  RULES = {
+    'max_title_length' => lambda do |post, option|
+      "title is longer than #{option} characters" if post.data['title'].to_s.length > option.to_i
+    end,
+    'require_description' => lambda do |post, _|
+      "description is missing" if post.data['description'].to_s.strip.empty?
+    end,
+    'featured_cover' => lambda do |post, _|
+      "featured post has no cover image" if post.data['featured'] && post.data['image'].to_s.empty?
+    end,
+    'allowed_tags' => lambda do |post, option|
+      unknown = Array(post.data['tags']) - Array(option)
+      "tags outside the vocabulary: #{unknown.join(', ')}" unless unknown.empty?
+    end,
+    'image_hosts' => lambda do |post, option|
+      allowed = Array(option) + Array(post.data['image_hosts']) + [URI(post.site.config['url'].to_s).host]
+      sources = post.content.scan(/!\[[^\]]*\]\(\s*<?([^)\s>]+)/).flatten +
+        post.content.scan(/<img[^>]*\ssrc=["']([^"']+)/i).flatten +
+        [post.data['image']].compact
+      hosts = sources.map { |src| src =~ %r{\A(?:https?:)?//([^/:]+)}i && $1.downcase }.compact.uniq
+      unexpected = hosts - allowed
+      "images from unexpected hosts: #{unexpected.join(', ')}" unless unexpected.empty?
+    end,
+    'headings_start_at_h2' => lambda do |post, _|
+      in_code = false
+      post.content.each_line do |line|
+        in_code = !in_code if line =~ /^\s*(```|~~~)/
+        return "content contains an h1, headings should start at h2" if !in_code && line =~ /^#\s/
+      end
+      nil
+    end,
+  }
+
+  # Returns [errors, warnings] as lists of messages.
+  def self.run(site)
+    errors, warnings = [], []
+    rules = site.config['lint'] || {}
+    unknown = rules.keys - RULES.keys
+    errors << "_config.yml: lint: unknown rules #{unknown.join(', ')}, expected one of #{RULES.keys.join(', ')}" unless unknown.empty?
+    site.posts.docs.each do |post|
+      ignored = Array(post.data['lint_ignore'])
+      unknown = ignored - RULES.keys
+      warnings << "#{post.relative_path}: lint_ignore: unknown rules #{unknown.join(', ')}" unless unknown.empty?
+      rules.each do |name, settings|
+        settings = { 'severity' => settings } unless settings.is_a?(Hash)
+        # YAML reads a bare `off` as false
+        severity = settings['severity'] == false ? 'off' : settings['severity'].to_s
+        next if severity == 'off' || ignored.include?(name) || !RULES.key?(name)
+        message = RULES[name].call(post, settings['value'])
+        next unless message
+        (severity == 'error' ? errors : warnings) << "#{post.relative_path}: #{name}: #{message}"
+      end
+    end
+    [errors, warnings]
+  end
+end
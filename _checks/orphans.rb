@@ -0,0 +1,41 @@
+require 'set'
+
+# Reports posts that none of the listing pages link to and static files that no
+# generated page or stylesheet references, run by `rake check`.
+module OrphanCheck
+  LISTINGS = ['index.html', 'page*/index.html', 'archive/index.html', 'tags/index.html']
+  SOURCES = '**/*.{html,xml,css,js,json,webmanifest}'
+
+  def self.run(site)
+    listed = links(site, LISTINGS.flat_map { |pattern| Dir.glob(File.join(site.dest, pattern)) })
+    referenced = links(site, Dir.glob(File.join(site.dest, SOURCES)))
+
+    issues = []
+    site.posts.docs.each do |post|
+      next if post.data['unlisted'] || post.data['hidden']
+      issues << "#{post.relative_path}: not linked from the index, archive or tag pages" unless listed.include?(post.url)
+    end
+    site.static_files.each do |file|
+      issues << "#{file.relative_path}: not referenced by any generated page" unless referenced.include?(file.url)
+    end
+    issues
+  end
+
+  # Collects the site-relative paths of every href, src and css url() in the given files.
+  def self.links(site, files)
+    host = site.config['url'].to_s
+    baseurl = %r{^#{Regexp.escape(site.baseurl.to_s)}(?=/|$)}
+    urls = Set.new
+    files.each do |file|
+      dir = File.dirname(file).sub(site.dest, '')
+      File.read(file).scan(/(?:href|src)=["']([^"']+)["']|url\(["']?([^"')]+)["']?\)/) do |attr, css|
+        url = (attr || css).sub(/[?#].*$/, '')
+        url = url.sub(host, '') if !host.empty? && url.start_with?(host)
+        next if url.empty? || url =~ %r{^([a-z]+:|//)}
+        path = url.start_with?('/') ? url.sub(baseurl, '') : File.expand_path(url, dir.empty? ? '/' : dir)
+        urls << path << path.sub(%r{index\.html$}, '')
+      end
+    end
+    urls
+  end
+end
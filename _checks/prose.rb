@@ -0,0 +1,53 @@
+require 'open3'
+require 'date'
+require 'yaml'
+
+# Spell checks the prose of markdown posts with aspell, run by `rake check prose=true`.
+# Front matter, code blocks, inline code, Liquid tags and URLs are skipped.
+module ProseCheck
+  FENCE = /^\s*(```|~~~)/
+
+  def self.run(files, config)
+    lang = config['lang'] || 'en'
+    ignore = Array(config['ignore']).map(&:downcase)
+    files.flat_map do |file|
+      source = File.read(file)
+      front = source =~ /\A---\s*\n(.*?)\n---\s*\n/m ? (YAML.safe_load($1, permitted_classes: [Date, Time]) || {}) : {}
+      lines = text_lines(source)
+      misspelled(lines.map(&:last).join("\n"), front['lang'] || lang, config['dictionary']).
+        reject { |word| ignore.include?(word.downcase) }.uniq.
+        map { |word| "#{file}:#{line_of(lines, word)}: #{word}" }
+    end
+  end
+
+  # Returns [line number, text] pairs for the lines containing prose.
+  def self.text_lines(source)
+    in_code = false
+    in_front = source.start_with?('---')
+    source.lines.each_with_index.map do |line, index|
+      if in_front
+        in_front = false if index > 0 && line.start_with?('---')
+        next
+      end
+      if line =~ FENCE
+        in_code = !in_code
+        next
+      end
+      next if in_code || line =~ /^( {4}|\t)/
+      [index + 1, line.gsub(/`[^`]*`/, '').gsub(/\{[%{].*?[%}]\}/, '').gsub(%r{\(?https?://\S+}, '')]
+    end.compact
+  end
+
+  def self.misspelled(text, lang, dictionary)
+    args = ['aspell', 'list', '--mode=markdown', "--lang=#{lang}"]
+    args << "--personal=#{File.expand_path(dictionary)}" if dictionary && File.exist?(dictionary)
+    out, status = Open3.capture2(*args, stdin_data: text)
+    abort("rake aborted: aspell failed, is it installed?") unless status.success?
+    out.split("\n")
+  end
+
+  def self.line_of(lines, word)
+    found = lines.find { |_, text| text =~ /\b#{Regexp.escape(word)}\b/ }
+    found ? found.first : 0
+  end
+end
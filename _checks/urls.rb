@@ -0,0 +1,27 @@
+# Flags internal links that don't follow the url policy, run by `rake check`:
+# when the `permalink` ends in a slash page links must too (or have a file
+# extension), and paths must not contain uppercase letters or spaces.
+module UrlCheck
+  def self.run(site)
+    permalink = site.config['permalink'].to_s
+    trailing_slash = permalink == 'pretty' || permalink.end_with?('/')
+    host = site.config['url'].to_s
+    issues = []
+    Dir.glob(File.join(site.dest, '**', '*.html')).each do |file|
+      File.read(file).each_line.with_index(1) do |line, number|
+        line.scan(/href=["']([^"'#?]+)/) do |(url)|
+          url = url.sub(host, '') if !host.empty? && url.start_with?(host)
+          next unless url.start_with?('/') && !url.start_with?('//')
+          name = file.sub("#{site.dest}/", '')
+          issues << "#{name}:#{number}: #{url} contains uppercase letters or spaces" if url =~ /[A-Z ]|%20/
+          if trailing_slash && File.extname(url).empty? && !url.end_with?('/')
+            issues << "#{name}:#{number}: #{url} is missing the trailing slash"
+          elsif !trailing_slash && url.length > 1 && url.end_with?('/')
+            issues << "#{name}:#{number}: #{url} has a trailing slash"
+          end
+        end
+      end
+    end
+    issues
+  end
+end
@@ -0,0 +1,75 @@
+require 'csv'
+require 'fileutils'
+require 'nokogiri'
+require 'open-uri'
+require 'time'
+require 'yaml'
+
+# Converts Medium and Substack export archives into posts, used by `rake import`.
+# Post bodies stay HTML, which kramdown passes through, and images are downloaded
+# into a folder per post so the posts don't depend on the old platform.
+module ArchiveImporter
+  Post = Struct.new(:title, :date, :description, :tags, :html)
+
+  # Medium exports every post as posts/<name>.html; drafts are prefixed with draft_.
+  def self.medium(dir)
+    Dir.glob(File.join(dir, "posts", "*.html")).reject { |file| File.basename(file).start_with?("draft_") }.map do |file|
+      doc = Nokogiri::HTML(File.read(file))
+      time = doc.at_css("time.dt-published")
+      body = doc.at_css("section[data-field=body]")
+      next unless time && body
+      body.css("h3.graf--title").first&.remove
+      Post.new(
+        doc.at_css("h1.p-name")&.text.to_s.strip,
+        Time.parse(time["datetime"]),
+        doc.at_css("section[data-field=subtitle]")&.text.to_s.strip,
+        [],
+        body.inner_html,
+      )
+    end.compact
+  end
+
+  # Substack exports posts.csv with the metadata and posts/<post_id>.html with the bodies.
+  def self.substack(dir)
+    CSV.foreach(File.join(dir, "posts.csv"), headers: true).map do |row|
+      next unless row["is_published"] == "true"
+      file = File.join(dir, "posts", "#{row["post_id"]}.html")
+      next unless File.exist?(file)
+      Post.new(row["title"].to_s.strip, Time.parse(row["post_date"]), row["subtitle"].to_s.strip, [], File.read(file))
+    end.compact
+  end
+
+  def self.write(posts, posts_dir, images_dir)
+    posts.each do |post|
+      slug = post.title.downcase.strip.gsub(' ', '-').gsub(/[^\w-]/, '')
+      date = post.date.strftime('%Y-%m-%d')
+      html = localize_images(post.html, File.join(images_dir, "#{date}-#{slug}"))
+      front = { 'layout' => 'post', 'title' => post.title, 'date' => post.date.strftime('%Y-%m-%d %H:%M:%S %z') }
+      front['description'] = post.description unless post.description.empty?
+      front['tags'] = post.tags unless post.tags.empty?
+      filename = File.join(posts_dir, "#{date}-#{slug}.html")
+      puts "Creating #{filename}"
+      File.write(filename, "#{front.to_yaml}---\n#{html.strip}\n")
+    end
+  end
+
+  # Downloads the images of a post into dir and points the img tags at the copies.
+  # The path includes the baseurl as is, because Nokogiri would url-escape a Liquid tag.
+  def self.localize_images(html, dir)
+    baseurl = YAML.load_file('_config.yml')['baseurl'].to_s
+    doc = Nokogiri::HTML.fragment(html)
+    doc.css("img[src^=http]").each_with_index do |img, index|
+      ext = File.extname(URI(img["src"]).path)
+      ext = ".jpg" if ext.empty?
+      FileUtils.mkdir_p(dir)
+      target = File.join(dir, "#{index + 1}#{ext}")
+      begin
+        URI.open(img["src"]) { |remote| File.binwrite(target, remote.read) }
+        img["src"] = "#{baseurl}/#{target.sub(%r{^\./}, '')}"
+      rescue StandardError => e
+        puts "warning: keeping remote image #{img["src"]}: #{e.message}"
+      end
+    end
+    doc.to_html
+  end
+end
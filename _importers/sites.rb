@@ -0,0 +1,94 @@
+require 'date'
+require 'fileutils'
+require 'time'
+require 'yaml'
+
+# Converts the posts of another Jekyll or Hugo site, used by `rake import`.
+# Front matter is normalized to the keys this site uses, old urls become
+# redirect_from aliases, and includes or shortcodes that have no counterpart
+# here are left as TODO comments.
+module SiteImporter
+  Post = Struct.new(:front, :body, :slug, :ext)
+
+  FRONT_MATTER = /\A(---|\+\+\+)\s*\n(.*?)\n\1\s*\n/m
+
+  # Jekyll keeps posts in _posts/, named YYYY-MM-DD-slug.ext.
+  def self.jekyll(dir)
+    Dir.glob(File.join(dir, "_posts", "**", "*.{md,markdown,html}")).map do |file|
+      front, body = split(File.read(file))
+      name = File.basename(file, ".*")
+      date = front['date'] || (name =~ /\A(\d{4}-\d{2}-\d{2})-/ && Date.parse($1))
+      next unless date
+      Post.new(normalize(front, date, front['permalink']), jekyll_body(body), name.sub(/\A\d{4}-\d{2}-\d{2}-/, ''), File.extname(file) == ".html" ? "html" : "md")
+    end.compact
+  end
+
+  # Hugo keeps content in content/, either as single files or as page bundles
+  # with an index.md; drafts are skipped.
+  def self.hugo(dir)
+    Dir.glob(File.join(dir, "content", "**", "*.md")).map do |file|
+      next if File.basename(file).start_with?("_index")
+      front, body = split(File.read(file))
+      next if front['draft']
+      date = front['date'] || front['publishDate']
+      next unless date
+      name = File.basename(file, ".*") == "index" ? File.basename(File.dirname(file)) : File.basename(file, ".*")
+      front['description'] ||= front['summary']
+      aliases = Array(front['aliases']) + Array(front['url'])
+      Post.new(normalize(front, date, aliases), hugo_body(body), front['slug'] || name, "md")
+    end.compact
+  end
+
+  # Returns the front matter as a hash, YAML between --- or TOML between +++, and the body.
+  def self.split(source)
+    return [{}, source] unless source =~ FRONT_MATTER
+    front = if $1 == '+++'
+      require 'tomlrb'
+      Tomlrb.parse($2)
+    else
+      YAML.safe_load($2, permitted_classes: [Date, Time]) || {}
+    end
+    [front, $']
+  end
+
+  def self.normalize(front, date, aliases)
+    date = Time.parse(date) if date.is_a?(String)
+    format = date.instance_of?(Date) ? '%Y-%m-%d' : '%Y-%m-%d %H:%M:%S %z'
+    result = { 'layout' => 'post', 'title' => front['title'].to_s, 'date' => date.strftime(format) }
+    result['description'] = front['description'].to_s unless front['description'].to_s.empty?
+    tags = front['tags'].is_a?(String) ? front['tags'].split : Array(front['tags'])
+    result['tags'] = tags unless tags.empty?
+    categories = Array(front['categories'] || front['category'])
+    categories = categories.first.split if categories.size == 1 && categories.first.is_a?(String)
+    result['categories'] = categories unless categories.empty?
+    redirects = Array(aliases).compact.map(&:to_s).reject(&:empty?)
+    result['redirect_from'] = redirects unless redirects.empty?
+    result
+  end
+
+  # Keeps includes that exist in this site's _includes/ and turns the others into TODO comments.
+  def self.jekyll_body(body)
+    body.gsub(/\{%-?\s*include\s+(\S+)(.*?)-?%\}/) do |tag|
+      name, args = $1, $2.strip
+      File.exist?(File.join("_includes", name)) ? tag : "<!-- TODO: untranslated include #{[name, args.gsub('--', '-')].reject(&:empty?).join(' ')} -->"
+    end
+  end
+
+  # Hugo shortcodes would be parsed as Liquid, so all of them become TODO comments.
+  def self.hugo_body(body)
+    body.gsub(/\{\{[<%]\s*(.*?)\s*[>%]\}\}/) { "<!-- TODO: untranslated shortcode #{$1.gsub('--', '-')} -->" }
+  end
+
+  def self.write(posts, posts_dir)
+    posts.each do |post|
+      slug = post.slug.downcase.strip.gsub(' ', '-').gsub(/[^\w-]/, '')
+      filename = File.join(posts_dir, "#{post.front['date'][0, 10]}-#{slug}.#{post.ext}")
+      if File.exist?(filename)
+        puts "warning: skipping #{filename}, it already exists"
+        next
+      end
+      puts "Creating #{filename}"
+      File.write(filename, "#{post.front.to_yaml}---\n#{post.body.strip}\n")
+    end
+  end
+end
@@ -0,0 +1,47 @@
+---
+layout: null
+sitemap: false
+---
+// Precaches the stylesheets and the most recent posts so they can be read offline.
+// Pages are fetched from the network first and only served from the cache when offline.
+{% assign posts = site.posts | where_exp: "post", "post.unlisted != true" | where_exp: "post", "post.hidden != true" | where_exp: "post", "post.status != 'archived'" %}
+var CACHE = 'blog-{{ site.time | date: "%s" }}';
+var PRECACHE = [
+  '{{ site.baseurl }}/',
+  '{{ site.baseurl }}/assets/css/main.css',
+  '{{ site.baseurl }}/assets/css/social-share-kit.css',
+  {% for post in posts limit: site.service_worker.posts %}
+  '{{ site.baseurl }}{{ post.url }}',
+  {% endfor %}
+];
+
+self.addEventListener('install', function(event) {
+  event.waitUntil(caches.open(CACHE).then(function(cache) {
+    return cache.addAll(PRECACHE);
+  }).then(function() {
+    return self.skipWaiting();
+  }));
+});
+
+self.addEventListener('activate', function(event) {
+  event.waitUntil(caches.keys().then(function(keys) {
+    return Promise.all(keys.filter(function(key) {
+      return key !== CACHE;
+    }).map(function(key) {
+      return caches.delete(key);
+    }));
+  }));
+});
+
+self.addEventListener('fetch', function(event) {
+  if (event.request.method !== 'GET' || new URL(event.request.url).origin !== location.origin) return;
+  event.respondWith(fetch(event.request).then(function(response) {
+    var copy = response.clone();
+    caches.open(CACHE).then(function(cache) {
+      cache.put(event.request, copy);
+    });
+    return response;
+  }).catch(function() {
+    return caches.match(event.request);
+  }));
+});